@@ -4,6 +4,10 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use clap::Clap;
 use futures::future::join_all;
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
 use yahoo_finance_api as yahoo;
 
 #[derive(clap::Clap)]
@@ -13,10 +17,112 @@ use yahoo_finance_api as yahoo;
     about = "Track stonk prices with ease!"
 )]
 struct Opts {
-    #[clap(short, long, default_value = "AAPL,MSFT,UBER,GOOG")]
-    symbols: String,
+    #[clap(short, long)]
+    symbols: Option<String>,
     #[clap(short, long)]
     from: String,
+    /// Keep running and re-fetch every `interval` seconds instead of exiting after one report.
+    /// Only applies to the default stats report; incompatible with `--signals`,
+    /// `--aggregate-out` and `--ws-url`, which each run their own single-shot or continuous
+    /// loop.
+    #[clap(short, long)]
+    interval: Option<u64>,
+    /// Path to a newline- or comma-delimited file of ticker symbols, merged with `--symbols`.
+    #[clap(long)]
+    symbols_file: Option<String>,
+    /// Maximum number of concurrent `fetch_closing_data` requests in flight at once.
+    #[clap(long, default_value = "10")]
+    max_concurrent: usize,
+    /// Websocket URL for a streaming ticker feed; when set, bypasses the REST poller entirely
+    /// and prints a fresh report in the `--output` format (or the signal rows, with
+    /// `--signals`) every time a new tick arrives.
+    #[clap(long)]
+    ws_url: Option<String>,
+    /// Size of the rolling per-symbol price window used to recompute signals from the
+    /// websocket feed.
+    #[clap(long, default_value = "30")]
+    buffer_size: usize,
+    /// Print buy/sell strategy signals instead of the descriptive stats report.
+    #[clap(long)]
+    signals: bool,
+    /// Fast/slow SMA windows for the crossover strategy, e.g. "50,200".
+    #[clap(long, default_value = "50,200")]
+    sma_windows: String,
+    /// Minimum favorable price move, as a fraction of the entry (or last scale-in) price,
+    /// required on a later bar to scale into an already-open position.
+    #[clap(long, default_value = "0.05")]
+    scale_in_threshold: f64,
+    /// Output format for the stats report: "csv", "json" or "table".
+    #[clap(long, default_value = "csv")]
+    output: String,
+    /// Collect every symbol's full history and indicator columns into a Polars DataFrame and
+    /// write it to this path instead of printing a report. Format (Parquet vs. CSV) is
+    /// inferred from the file extension.
+    #[clap(long)]
+    aggregate_out: Option<String>,
+}
+
+///
+/// The rendering format for a stats report.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OutputFormat {
+    Csv,
+    Json,
+    Table,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(OutputFormat::Csv),
+            "json" => Ok(OutputFormat::Json),
+            "table" => Ok(OutputFormat::Table),
+            other => Err(format!(
+                "unknown '--output' format '{}', expected csv, json or table",
+                other
+            )),
+        }
+    }
+}
+
+/// The ticker universe used when neither `--symbols` nor `--symbols-file` is given.
+const DEFAULT_SYMBOLS: &str = "AAPL,MSFT,UBER,GOOG";
+
+///
+/// Parse the `--symbols` value and the optional `--symbols-file` contents into a deduplicated
+/// list of ticker symbols. `DEFAULT_SYMBOLS` only kicks in when both are omitted, so
+/// `--symbols-file` can stand alone without the default tickers leaking into the universe.
+///
+fn load_symbols(opts: &Opts) -> std::io::Result<Vec<String>> {
+    let explicit_symbols = match &opts.symbols {
+        Some(s) => s.as_str(),
+        None if opts.symbols_file.is_none() => DEFAULT_SYMBOLS,
+        None => "",
+    };
+
+    let mut symbols: Vec<String> = explicit_symbols
+        .split(",")
+        .map(|s| s.trim().to_owned())
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    if let Some(path) = &opts.symbols_file {
+        let contents = std::fs::read_to_string(path)?;
+        for symbol in contents.split(|c| c == '\n' || c == ',') {
+            let symbol = symbol.trim();
+            if !symbol.is_empty() {
+                symbols.push(symbol.to_owned());
+            }
+        }
+    }
+
+    symbols.sort();
+    symbols.dedup();
+
+    Ok(symbols)
 }
 
 struct StockHistory {
@@ -24,21 +130,34 @@ struct StockHistory {
     closes: Vec<f64>,
 }
 
+#[derive(Serialize)]
 struct StockStats {
+    period_start: String,
     symbol: String,
     last_price: f64,
     pct_change: f64,
     period_min: f64,
     period_max: f64,
     thirty_day_avg: f64,
+    twelve_day_ema: f64,
+    rsi: f64,
+    macd: (f64, f64, f64),
+    bollinger_bands: (f64, f64, f64),
 }
 
 impl StockStats {
-    async fn new(symbol: String, closes: Vec<f64>) -> Self {
+    async fn new(symbol: String, closes: Vec<f64>, period_start: DateTime<Utc>) -> Self {
         let min_price = MinPrice {};
         let max_price = MaxPrice {};
         let price_diff = PriceDiff {};
         let windowed_sma = WindowedSMA { window_size: 30 };
+        let ema = Ema { period: 12 };
+        let rsi = Rsi { period: 14 };
+        let macd = Macd {};
+        let bollinger_bands = BollingerBands {
+            window_size: 20,
+            num_std_dev: 2.0,
+        };
 
         let last_price = *closes.last().unwrap_or(&0.0);
         let (_, pct_change) = price_diff.calculate(&closes).await.unwrap();
@@ -46,14 +165,27 @@ impl StockStats {
         let period_max = max_price.calculate(&closes).await.unwrap();
         let sma = windowed_sma.calculate(&closes).await.unwrap_or_default();
         let thirty_day_avg = *sma.last().unwrap_or(&0.0);
+        let ema_values = ema.calculate(&closes).await.unwrap_or_default();
+        let twelve_day_ema = *ema_values.last().unwrap_or(&0.0);
+        let rsi_values = rsi.calculate(&closes).await.unwrap_or_default();
+        let rsi = *rsi_values.last().unwrap_or(&0.0);
+        let macd_values = macd.calculate(&closes).await.unwrap_or_default();
+        let macd = *macd_values.last().unwrap_or(&(0.0, 0.0, 0.0));
+        let bollinger_values = bollinger_bands.calculate(&closes).await.unwrap_or_default();
+        let bollinger_bands = *bollinger_values.last().unwrap_or(&(0.0, 0.0, 0.0));
 
         StockStats {
+            period_start: period_start.to_rfc3339(),
             symbol,
             last_price,
             pct_change,
             period_min,
             period_max,
             thirty_day_avg,
+            twelve_day_ema,
+            rsi,
+            macd,
+            bollinger_bands,
         }
     }
 }
@@ -105,6 +237,45 @@ struct WindowedSMA {
     window_size: usize,
 }
 
+///
+/// Calculate an exponential moving average of a f64 series, smoothed with `alpha = 2/(n+1)`
+/// and seeded with the first price in the series.
+///
+struct Ema {
+    period: usize,
+}
+
+///
+/// Calculate the Relative Strength Index over a f64 series using Wilder smoothing of the
+/// average gain/loss over `period` close-to-close deltas.
+///
+struct Rsi {
+    period: usize,
+}
+
+///
+/// Calculate the Moving Average Convergence/Divergence of a f64 series, using the standard
+/// 12/26/9-period EMAs.
+///
+/// # Returns
+///
+/// A series of `(macd_line, signal_line, histogram)` tuples.
+///
+struct Macd;
+
+///
+/// Calculate Bollinger Bands over a f64 series using a windowed SMA as the middle band and
+/// the population standard deviation of each window for the upper/lower bands.
+///
+/// # Returns
+///
+/// A series of `(middle, upper, lower)` tuples.
+///
+struct BollingerBands {
+    window_size: usize,
+    num_std_dev: f64,
+}
+
 #[async_trait]
 impl StockSignal for MinPrice {
     type SignalType = f64;
@@ -173,6 +344,234 @@ impl StockSignal for WindowedSMA {
     }
 }
 
+#[async_trait]
+impl StockSignal for Ema {
+    type SignalType = Vec<f64>;
+
+    async fn calculate(&self, series: &[f64]) -> Option<Self::SignalType> {
+        if series.is_empty() {
+            return None;
+        }
+
+        let alpha = 2.0 / (self.period as f64 + 1.0);
+        let mut ema = Vec::with_capacity(series.len());
+        let mut prev_ema = series[0];
+        ema.push(prev_ema);
+
+        for price in &series[1..] {
+            prev_ema = alpha * price + (1.0 - alpha) * prev_ema;
+            ema.push(prev_ema);
+        }
+
+        Some(ema)
+    }
+}
+
+impl Rsi {
+    fn from_averages(avg_gain: f64, avg_loss: f64) -> f64 {
+        if avg_loss == 0.0 {
+            100.0
+        } else {
+            100.0 - 100.0 / (1.0 + avg_gain / avg_loss)
+        }
+    }
+}
+
+#[async_trait]
+impl StockSignal for Rsi {
+    type SignalType = Vec<f64>;
+
+    async fn calculate(&self, series: &[f64]) -> Option<Self::SignalType> {
+        if series.len() <= self.period {
+            return None;
+        }
+
+        let deltas: Vec<f64> = series.windows(2).map(|w| w[1] - w[0]).collect();
+        let gains: Vec<f64> = deltas.iter().map(|d| d.max(0.0)).collect();
+        let losses: Vec<f64> = deltas.iter().map(|d| (-d).max(0.0)).collect();
+        let period = self.period as f64;
+
+        let mut avg_gain = gains[..self.period].iter().sum::<f64>() / period;
+        let mut avg_loss = losses[..self.period].iter().sum::<f64>() / period;
+
+        let mut rsi = Vec::with_capacity(deltas.len() - self.period + 1);
+        rsi.push(Rsi::from_averages(avg_gain, avg_loss));
+
+        for i in self.period..deltas.len() {
+            avg_gain = (avg_gain * (period - 1.0) + gains[i]) / period;
+            avg_loss = (avg_loss * (period - 1.0) + losses[i]) / period;
+            rsi.push(Rsi::from_averages(avg_gain, avg_loss));
+        }
+
+        Some(rsi)
+    }
+}
+
+#[async_trait]
+impl StockSignal for Macd {
+    type SignalType = Vec<(f64, f64, f64)>;
+
+    async fn calculate(&self, series: &[f64]) -> Option<Self::SignalType> {
+        let ema12 = Ema { period: 12 }.calculate(series).await?;
+        let ema26 = Ema { period: 26 }.calculate(series).await?;
+
+        let macd_line: Vec<f64> = ema12
+            .iter()
+            .zip(ema26.iter())
+            .map(|(fast, slow)| fast - slow)
+            .collect();
+        let signal_line = Ema { period: 9 }.calculate(&macd_line).await?;
+
+        Some(
+            macd_line
+                .iter()
+                .zip(signal_line.iter())
+                .map(|(macd, signal)| (*macd, *signal, macd - signal))
+                .collect(),
+        )
+    }
+}
+
+#[async_trait]
+impl StockSignal for BollingerBands {
+    type SignalType = Vec<(f64, f64, f64)>;
+
+    async fn calculate(&self, series: &[f64]) -> Option<Self::SignalType> {
+        if series.is_empty() || self.window_size < 2 {
+            return None;
+        }
+
+        Some(
+            series
+                .windows(self.window_size)
+                .map(|w| {
+                    let middle = w.iter().sum::<f64>() / w.len() as f64;
+                    let variance =
+                        w.iter().map(|x| (x - middle).powi(2)).sum::<f64>() / w.len() as f64;
+                    let std_dev = variance.sqrt();
+                    (
+                        middle,
+                        middle + self.num_std_dev * std_dev,
+                        middle - self.num_std_dev * std_dev,
+                    )
+                })
+                .collect(),
+        )
+    }
+}
+
+///
+/// A discrete trade signal emitted by a strategy, as opposed to the descriptive `StockSignal`
+/// values reported by `StockStats`.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TradeSignal {
+    GoLong,
+    GoShort,
+    ExitLong,
+    ScaleIn,
+}
+
+///
+/// The strategy's current open position, if any, and the price it was entered or last scaled
+/// into at. Tracked so a later bar that has moved far enough in the position's favor can emit
+/// a `ScaleIn`.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Position {
+    Flat,
+    Long(f64),
+    Short(f64),
+}
+
+///
+/// Fast/slow SMA-crossover strategy, always in the market once a first crossing fires: a
+/// down-cross while long emits `ExitLong` and flips straight to short, an up-cross while short
+/// flips back to long, and a down-cross from flat (no prior long) emits `GoShort`. While a
+/// position stays open, emits an additional `ScaleIn` on any later bar where the price has
+/// moved `scale_in_threshold` further in its favor, since crossings alternate and can never
+/// repeat in the same direction on their own.
+///
+struct SmaCrossoverStrategy {
+    fast_window: usize,
+    slow_window: usize,
+    scale_in_threshold: f64,
+}
+
+impl SmaCrossoverStrategy {
+    async fn generate(&self, closes: &[f64]) -> Vec<TradeSignal> {
+        let fast = WindowedSMA {
+            window_size: self.fast_window,
+        }
+        .calculate(closes)
+        .await
+        .unwrap_or_default();
+        let slow = WindowedSMA {
+            window_size: self.slow_window,
+        }
+        .calculate(closes)
+        .await
+        .unwrap_or_default();
+
+        // A `WindowedSMA` over a larger window produces fewer points, so the fast SMA is only
+        // guaranteed to be at least as long as the slow one when `fast_window <= slow_window`.
+        // An inverted config (fast window wider than slow) would underflow the offset below.
+        if fast.len() < 2 || slow.len() < 2 || fast.len() < slow.len() {
+            return vec![];
+        }
+
+        // Both SMAs are windowed over the same `closes`, so the slower one starts later; trim
+        // the fast SMA and the matching prices to line up on the same bar index.
+        let offset = fast.len() - slow.len();
+        let fast = &fast[offset..];
+        let prices = &closes[closes.len() - slow.len()..];
+
+        let mut signals = Vec::new();
+        let mut position = Position::Flat;
+
+        for i in 1..slow.len() {
+            let price = prices[i];
+            let was_above = fast[i - 1] > slow[i - 1];
+            let is_above = fast[i] > slow[i];
+
+            if is_above && !was_above {
+                // A long position flips straight to short on a down-cross (see below), so this
+                // branch only ever sees a Flat or Short position to reverse out of.
+                signals.push(TradeSignal::GoLong);
+                position = Position::Long(price);
+                continue;
+            } else if !is_above && was_above {
+                // Exiting a long is worth naming distinctly from opening a fresh short, even
+                // though both reverse the position to `Short` below.
+                if matches!(position, Position::Long(_)) {
+                    signals.push(TradeSignal::ExitLong);
+                } else {
+                    signals.push(TradeSignal::GoShort);
+                }
+                position = Position::Short(price);
+                continue;
+            }
+
+            // Crossings strictly alternate, so a repeat same-direction crossing can never
+            // happen while a position stays open; re-check the scale-in condition on every bar
+            // instead so a strong trend can keep scaling in without a new crossing.
+            match position {
+                Position::Long(entry) if price >= entry * (1.0 + self.scale_in_threshold) => {
+                    signals.push(TradeSignal::ScaleIn);
+                    position = Position::Long(price);
+                }
+                Position::Short(entry) if price <= entry * (1.0 - self.scale_in_threshold) => {
+                    signals.push(TradeSignal::ScaleIn);
+                    position = Position::Short(price);
+                }
+                _ => {}
+            }
+        }
+
+        signals
+    }
+}
+
 ///
 /// Fetch the closing prices of a stonk over a period of time.
 ///
@@ -205,35 +604,523 @@ async fn fetch_closing_data(
     })
 }
 
-#[tokio::main]
-async fn main() -> std::io::Result<()> {
-    let opts: Opts = Opts::parse();
-    let from: DateTime<Utc> = opts.from.parse().expect("Couldn't parse the 'from' date.");
-    let to: DateTime<Utc> = Utc::now();
-    let symbols = opts.symbols.split(",");
+///
+/// A source of price history for a symbol. `fetch_closing_data` talks to the Yahoo REST API;
+/// implementing this trait lets another backend serve a snapshot through the same interface,
+/// whether that's `print_report`/`print_signals`/`write_aggregate` pulling a historical range
+/// from `YahooPriceSource`, or `WebSocketPriceSource::run` pulling its own live ring buffer on
+/// every tick.
+///
+#[async_trait]
+trait PriceSource {
+    async fn fetch(
+        &self,
+        symbol: &str,
+        from: &DateTime<Utc>,
+        to: &DateTime<Utc>,
+    ) -> std::io::Result<StockHistory>;
+}
+
+///
+/// The default `PriceSource`, backed by the Yahoo Finance REST API.
+///
+struct YahooPriceSource;
+
+#[async_trait]
+impl PriceSource for YahooPriceSource {
+    async fn fetch(
+        &self,
+        symbol: &str,
+        from: &DateTime<Utc>,
+        to: &DateTime<Utc>,
+    ) -> std::io::Result<StockHistory> {
+        fetch_closing_data(symbol, from, to).await
+    }
+}
+
+///
+/// A single message received from the streaming ticker feed. System-status and unrecognised
+/// frames are kept separate from price ticks so callers can skip them without inspecting
+/// individual fields.
+///
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum TickEvent {
+    SystemStatus {
+        status: String,
+    },
+    TickerData {
+        symbol: String,
+        bid: f64,
+        ask: f64,
+        last: f64,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+///
+/// A fixed-capacity ring buffer of the most recent prices seen for a symbol, used to feed the
+/// `StockSignal` pipeline from a live tick stream instead of a historical series.
+///
+struct PriceRingBuffer {
+    capacity: usize,
+    prices: std::collections::VecDeque<f64>,
+}
+
+impl PriceRingBuffer {
+    fn new(capacity: usize) -> Self {
+        PriceRingBuffer {
+            capacity,
+            prices: std::collections::VecDeque::with_capacity(capacity),
+        }
+    }
+
+    fn push(&mut self, price: f64) {
+        if self.prices.len() == self.capacity {
+            self.prices.pop_front();
+        }
+        self.prices.push_back(price);
+    }
+
+    fn as_vec(&self) -> Vec<f64> {
+        self.prices.iter().copied().collect()
+    }
+}
+
+/// How long to wait before retrying the websocket connection, whether it failed to connect or
+/// simply closed cleanly.
+const WEBSOCKET_RECONNECT_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+
+///
+/// What `WebSocketPriceSource::run` should do with each symbol's buffered snapshot as a new
+/// tick arrives: print the same descriptive stats report `--output` would, or the same
+/// crossover strategy signal stream `--signals` would.
+///
+enum WsAction<'a> {
+    Stats(OutputFormat),
+    Signals(&'a SmaCrossoverStrategy),
+}
 
-    let stock_histories = join_all(symbols.map(|s| fetch_closing_data(s, &from, &to))).await;
+///
+/// A live `PriceSource` backed by a streaming websocket ticker feed. `run` owns the connection
+/// and keeps a per-symbol ring buffer of recent prices up to date as ticks arrive, then drives
+/// `fetch`'s buffered snapshot through the same report/signal rendering the REST poller uses.
+///
+struct WebSocketPriceSource {
+    buffer_size: usize,
+    buffers: std::sync::Arc<tokio::sync::Mutex<std::collections::HashMap<String, PriceRingBuffer>>>,
+}
+
+impl WebSocketPriceSource {
+    fn new(buffer_size: usize) -> Self {
+        WebSocketPriceSource {
+            buffer_size,
+            buffers: std::sync::Arc::new(tokio::sync::Mutex::new(std::collections::HashMap::new())),
+        }
+    }
+
+    ///
+    /// Connect to `url` and, for every symbol a new tick arrives for, apply `action` to the
+    /// buffer's up-to-date snapshot: print a fresh stats row, or every signal the strategy
+    /// generates over the whole buffer, instead of waiting for the next daily close. Reconnects with `WEBSOCKET_RECONNECT_DELAY` backoff
+    /// both when the connection attempt fails and when an established stream ends (cleanly or
+    /// otherwise), so a server that keeps closing the socket can't cause a busy-loop. Skips
+    /// heartbeat/metadata frames.
+    ///
+    async fn run(&self, url: &str, action: WsAction<'_>) -> std::io::Result<()> {
+        loop {
+            let ws_stream = match connect_async(url).await {
+                Ok((stream, _)) => stream,
+                Err(_) => {
+                    tokio::time::sleep(WEBSOCKET_RECONNECT_DELAY).await;
+                    continue;
+                }
+            };
+
+            let (_write, mut read) = ws_stream.split();
+
+            while let Some(message) = read.next().await {
+                let text = match message {
+                    Ok(Message::Text(text)) => text,
+                    Ok(_) => continue,
+                    Err(_) => break,
+                };
+
+                let event: TickEvent = match serde_json::from_str(&text) {
+                    Ok(event) => event,
+                    Err(_) => continue,
+                };
+
+                let (symbol, last) = match event {
+                    TickEvent::TickerData { symbol, last, .. } => (symbol, last),
+                    TickEvent::SystemStatus { .. } | TickEvent::Unknown => continue,
+                };
+
+                {
+                    let mut buffers = self.buffers.lock().await;
+                    let buffer = buffers
+                        .entry(symbol.clone())
+                        .or_insert_with(|| PriceRingBuffer::new(self.buffer_size));
+                    buffer.push(last);
+                }
+
+                let now = Utc::now();
+                let history = self.fetch(&symbol, &now, &now).await?;
+
+                match &action {
+                    WsAction::Stats(format) => {
+                        let stats = StockStats::new(history.symbol, history.closes, now).await;
+                        println!("{}", format_stats(*format, &[stats]));
+                    }
+                    WsAction::Signals(strategy) => {
+                        for signal in strategy.generate(&history.closes).await {
+                            println!("{},{},{:?}", now.to_rfc3339(), history.symbol, signal);
+                        }
+                    }
+                }
+            }
+
+            // The stream ended, whether cleanly or via an error above — back off before the
+            // next reconnect attempt instead of hammering a server that keeps closing us.
+            tokio::time::sleep(WEBSOCKET_RECONNECT_DELAY).await;
+        }
+    }
+}
+
+#[async_trait]
+impl PriceSource for WebSocketPriceSource {
+    async fn fetch(
+        &self,
+        symbol: &str,
+        _from: &DateTime<Utc>,
+        _to: &DateTime<Utc>,
+    ) -> std::io::Result<StockHistory> {
+        let buffers = self.buffers.lock().await;
+        let closes = buffers.get(symbol).map(|b| b.as_vec()).unwrap_or_default();
+
+        Ok(StockHistory {
+            symbol: symbol.to_owned(),
+            closes,
+        })
+    }
+}
+
+///
+/// Render a batch of `StockStats` in the requested `OutputFormat`. CSV and JSON emit one line
+/// per symbol; the table format additionally pads each column for readability on a terminal.
+///
+fn format_stats(format: OutputFormat, stats: &[StockStats]) -> String {
+    match format {
+        OutputFormat::Csv => stats
+            .iter()
+            .map(|s| {
+                let (macd_line, signal_line, histogram) = s.macd;
+                let (bb_middle, bb_upper, bb_lower) = s.bollinger_bands;
+                format!(
+                    "{},{},${:.2},{:.2}%,${:.2},${:.2},${:.2},${:.2},{:.2},{:.4},{:.4},{:.4},${:.2},${:.2},${:.2}",
+                    s.period_start,
+                    s.symbol,
+                    s.last_price,
+                    s.pct_change,
+                    s.period_min,
+                    s.period_max,
+                    s.thirty_day_avg,
+                    s.twelve_day_ema,
+                    s.rsi,
+                    macd_line,
+                    signal_line,
+                    histogram,
+                    bb_middle,
+                    bb_upper,
+                    bb_lower,
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+        OutputFormat::Json => stats
+            .iter()
+            .filter_map(|s| serde_json::to_string(s).ok())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        OutputFormat::Table => {
+            let mut rows = vec![format!(
+                "{:<8}{:>10}{:>10}{:>10}{:>10}{:>10}",
+                "symbol", "price", "change %", "min", "max", "30d avg"
+            )];
+            rows.extend(stats.iter().map(|s| {
+                format!(
+                    "{:<8}{:>10.2}{:>10.2}{:>10.2}{:>10.2}{:>10.2}",
+                    s.symbol, s.last_price, s.pct_change, s.period_min, s.period_max, s.thirty_day_avg
+                )
+            }));
+            rows.join("\n")
+        }
+    }
+}
+
+///
+/// Fetch closing data for every symbol over `[from, to]`, compute `StockStats` for each and
+/// print them in the given `OutputFormat`.
+///
+async fn print_report(
+    source: &dyn PriceSource,
+    symbols: &[String],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    max_concurrent: usize,
+    format: OutputFormat,
+) {
+    let stock_histories: Vec<_> = stream::iter(symbols.iter())
+        .map(|s| source.fetch(s, &from, &to))
+        .buffer_unordered(max_concurrent)
+        .collect()
+        .await;
 
     let stock_stats: Vec<_> = join_all(
         stock_histories
             .into_iter()
             .filter_map(|r| r.ok())
-            .map(|s| StockStats::new(s.symbol.to_owned(), s.closes.to_owned())),
+            .map(|s| StockStats::new(s.symbol.to_owned(), s.closes.to_owned(), from)),
     )
     .await;
 
-    println!("period start,symbol,price,change %,min,max,30d avg");
-    for stats in stock_stats {
+    println!("{}", format_stats(format, &stock_stats));
+}
+
+///
+/// Fetch closing data for every symbol over `[from, to]` and print one CSV row per trade
+/// signal the crossover strategy emits, instead of the descriptive stats report.
+///
+async fn print_signals(
+    source: &dyn PriceSource,
+    symbols: &[String],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    max_concurrent: usize,
+    strategy: &SmaCrossoverStrategy,
+) {
+    let stock_histories: Vec<_> = stream::iter(symbols.iter())
+        .map(|s| source.fetch(s, &from, &to))
+        .buffer_unordered(max_concurrent)
+        .collect()
+        .await;
+
+    for history in stock_histories.into_iter().filter_map(|r| r.ok()) {
+        for signal in strategy.generate(&history.closes).await {
+            println!("{},{},{:?}", to.to_rfc3339(), history.symbol, signal);
+        }
+    }
+}
+
+///
+/// Fetch every symbol's full closing-price history plus its percent-change/EMA/RSI indicator
+/// columns into a single Polars `DataFrame`, then write it to `path` so it can be sorted by
+/// `pct_change` to filter for the best and worst performers. The output format (Parquet vs.
+/// CSV) is inferred from the extension.
+///
+async fn write_aggregate(
+    source: &dyn PriceSource,
+    symbols: &[String],
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    max_concurrent: usize,
+    path: &str,
+) -> std::io::Result<()> {
+    use polars::prelude::*;
+
+    let histories: Vec<_> = stream::iter(symbols.iter())
+        .map(|s| source.fetch(s, &from, &to))
+        .buffer_unordered(max_concurrent)
+        .collect()
+        .await;
+
+    let mut symbol_col = Vec::new();
+    let mut timestamp_col = Vec::new();
+    let mut close_col = Vec::new();
+    let mut pct_change_col = Vec::new();
+    let mut ema_col = Vec::new();
+    let mut rsi_col = Vec::new();
+
+    for history in histories.into_iter().filter_map(|r| r.ok()) {
+        let rsi_period = Rsi { period: 14 };
+        let ema = Ema { period: 12 }
+            .calculate(&history.closes)
+            .await
+            .unwrap_or_default();
+        let rsi = rsi_period
+            .calculate(&history.closes)
+            .await
+            .unwrap_or_default();
+
+        for (i, close) in history.closes.iter().enumerate() {
+            symbol_col.push(history.symbol.clone());
+            timestamp_col.push(to.to_rfc3339());
+            close_col.push(*close);
+            pct_change_col.push(
+                i.checked_sub(1)
+                    .and_then(|j| history.closes.get(j))
+                    .map(|prev| (close - prev) / prev * 100.0)
+                    .unwrap_or(f64::NAN),
+            );
+            ema_col.push(ema.get(i).copied().unwrap_or(f64::NAN));
+            rsi_col.push(
+                i.checked_sub(rsi_period.period)
+                    .and_then(|j| rsi.get(j))
+                    .copied()
+                    .unwrap_or(f64::NAN),
+            );
+        }
+    }
+
+    let mut df = DataFrame::new(vec![
+        Series::new("symbol", symbol_col),
+        Series::new("timestamp", timestamp_col),
+        Series::new("close", close_col),
+        Series::new("pct_change", pct_change_col),
+        Series::new("ema", ema_col),
+        Series::new("rsi", rsi_col),
+    ])
+    .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+
+    let mut file = std::fs::File::create(path)?;
+    if path.ends_with(".parquet") {
+        ParquetWriter::new(&mut file)
+            .finish(&mut df)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    } else {
+        CsvWriter::new(&mut file)
+            .finish(&mut df)
+            .map_err(|e| Error::new(ErrorKind::Other, e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+///
+/// Parse `--sma-windows` (e.g. `"50,200"`) into `(fast_window, slow_window)`, rejecting a
+/// fast window that isn't strictly smaller than the slow one instead of letting
+/// `SmaCrossoverStrategy::generate` silently produce no signals for an inverted config.
+///
+fn parse_sma_windows(s: &str) -> std::io::Result<(usize, usize)> {
+    let (fast, slow) = s
+        .split_once(",")
+        .and_then(|(fast, slow)| {
+            Some((
+                fast.trim().parse::<usize>().ok()?,
+                slow.trim().parse::<usize>().ok()?,
+            ))
+        })
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "couldn't parse '--sma-windows={}', expected e.g. \"50,200\"",
+                    s
+                ),
+            )
+        })?;
+
+    if fast >= slow {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "'--sma-windows={}' must have fast < slow, got fast={} slow={}",
+                s, fast, slow
+            ),
+        ));
+    }
+
+    Ok((fast, slow))
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let opts: Opts = Opts::parse();
+    let from: DateTime<Utc> = opts.from.parse().expect("Couldn't parse the 'from' date.");
+    let to: DateTime<Utc> = Utc::now();
+    let lookback = to - from;
+    let symbols = load_symbols(&opts)?;
+    let max_concurrent = opts.max_concurrent;
+
+    if max_concurrent == 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "--max-concurrent must be at least 1",
+        ));
+    }
+
+    if opts.interval.is_some()
+        && (opts.signals || opts.aggregate_out.is_some() || opts.ws_url.is_some())
+    {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "--interval only applies to the default stats report; it has no effect with \
+             --signals, --aggregate-out or --ws-url",
+        ));
+    }
+
+    if opts.aggregate_out.is_some() && opts.ws_url.is_some() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "--aggregate-out can't be combined with --ws-url: aggregate export collects a \
+             bounded historical range, not a live feed",
+        ));
+    }
+
+    let source = YahooPriceSource;
+
+    if let Some(path) = &opts.aggregate_out {
+        return write_aggregate(&source, &symbols, from, to, max_concurrent, path).await;
+    }
+
+    if opts.signals {
+        let (fast_window, slow_window) = parse_sma_windows(&opts.sma_windows)?;
+        let strategy = SmaCrossoverStrategy {
+            fast_window,
+            slow_window,
+            scale_in_threshold: opts.scale_in_threshold,
+        };
+
+        println!("period start,symbol,signal");
+
+        if let Some(url) = &opts.ws_url {
+            return WebSocketPriceSource::new(opts.buffer_size)
+                .run(url, WsAction::Signals(&strategy))
+                .await;
+        }
+
+        print_signals(&source, &symbols, from, to, max_concurrent, &strategy).await;
+        return Ok(());
+    }
+
+    let format: OutputFormat = opts
+        .output
+        .parse()
+        .expect("Couldn't parse the '--output' format.");
+
+    if format == OutputFormat::Csv {
         println!(
-            "{},{},${:.2},{:.2}%,${:.2},${:.2},${:.2}",
-            from.to_rfc3339(),
-            stats.symbol,
-            stats.last_price,
-            stats.pct_change,
-            stats.period_min,
-            stats.period_max,
-            stats.thirty_day_avg,
-        )
+            "period start,symbol,price,change %,min,max,30d avg,12d ema,rsi,macd,macd signal,macd histogram,bb middle,bb upper,bb lower"
+        );
+    }
+
+    if let Some(url) = &opts.ws_url {
+        return WebSocketPriceSource::new(opts.buffer_size)
+            .run(url, WsAction::Stats(format))
+            .await;
+    }
+
+    match opts.interval {
+        Some(secs) => {
+            let mut ticker = tokio::time::interval(std::time::Duration::from_secs(secs));
+            loop {
+                ticker.tick().await;
+                let to = Utc::now();
+                print_report(&source, &symbols, to - lookback, to, max_concurrent, format).await;
+            }
+        }
+        None => print_report(&source, &symbols, from, to, max_concurrent, format).await,
     }
 
     Ok(())
@@ -244,6 +1131,83 @@ mod tests {
     #![allow(non_snake_case)]
     use super::*;
 
+    fn base_opts() -> Opts {
+        Opts {
+            symbols: None,
+            from: String::new(),
+            interval: None,
+            symbols_file: None,
+            max_concurrent: 10,
+            ws_url: None,
+            buffer_size: 30,
+            signals: false,
+            sma_windows: "50,200".to_owned(),
+            scale_in_threshold: 0.05,
+            output: "csv".to_owned(),
+            aggregate_out: None,
+        }
+    }
+
+    /// Writes `contents` to a uniquely-named file under the system temp dir and returns its
+    /// path, so `load_symbols` tests don't collide when run in parallel.
+    fn write_temp_symbols_file(name: &str, contents: &str) -> String {
+        let path = std::env::temp_dir().join(format!(
+            "stonks_test_load_symbols_{}_{}.txt",
+            name,
+            std::process::id()
+        ));
+        std::fs::write(&path, contents).unwrap();
+        path.to_str().unwrap().to_owned()
+    }
+
+    #[test]
+    fn test_load_symbols_defaults_when_nothing_given() {
+        let opts = base_opts();
+
+        let symbols = load_symbols(&opts).unwrap();
+
+        assert_eq!(symbols, vec!["AAPL", "GOOG", "MSFT", "UBER"]);
+    }
+
+    #[test]
+    fn test_load_symbols_symbols_file_suppresses_default() {
+        let mut opts = base_opts();
+        opts.symbols_file = Some(write_temp_symbols_file("suppresses_default", "TSLA\nNFLX"));
+
+        let symbols = load_symbols(&opts).unwrap();
+
+        // Regression for 41b8968: --symbols-file alone must not pull in DEFAULT_SYMBOLS.
+        assert_eq!(symbols, vec!["NFLX", "TSLA"]);
+    }
+
+    #[test]
+    fn test_load_symbols_merges_symbols_and_file_with_dedup() {
+        let mut opts = base_opts();
+        opts.symbols = Some("AAPL,TSLA".to_owned());
+        opts.symbols_file = Some(write_temp_symbols_file(
+            "merges_and_dedups",
+            "TSLA,NFLX\nMSFT",
+        ));
+
+        let symbols = load_symbols(&opts).unwrap();
+
+        assert_eq!(symbols, vec!["AAPL", "MSFT", "NFLX", "TSLA"]);
+    }
+
+    #[test]
+    fn test_load_symbols_trims_blank_and_whitespace_entries() {
+        let mut opts = base_opts();
+        opts.symbols = Some(" AAPL , ,  ".to_owned());
+        opts.symbols_file = Some(write_temp_symbols_file(
+            "trims_whitespace",
+            "\n\n TSLA \n,,\n",
+        ));
+
+        let symbols = load_symbols(&opts).unwrap();
+
+        assert_eq!(symbols, vec!["AAPL", "TSLA"]);
+    }
+
     #[tokio::test]
     async fn test_MinPrice_calculate() {
         let signal = MinPrice {};
@@ -314,4 +1278,127 @@ mod tests {
         let signal = WindowedSMA { window_size: 10 };
         assert_eq!(signal.calculate(&series).await, Some(vec![]));
     }
+
+    #[tokio::test]
+    async fn test_Ema_calculate() {
+        let signal = Ema { period: 2 };
+        assert_eq!(signal.calculate(&[]).await, None);
+        assert_eq!(
+            signal.calculate(&[2.0, 4.0, 6.0]).await,
+            Some(vec![2.0, 3.333333333333333, 5.111111111111111])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_Rsi_calculate() {
+        let signal = Rsi { period: 3 };
+        let series = [44.0, 44.5, 43.0, 44.0, 45.0, 46.0, 45.5];
+
+        assert_eq!(signal.calculate(&[1.0, 2.0, 3.0]).await, None);
+
+        let rsi = signal.calculate(&series).await.unwrap();
+        assert_eq!(rsi.len(), series.len() - signal.period);
+        assert!(rsi.iter().all(|v| (0.0..=100.0).contains(v)));
+    }
+
+    #[tokio::test]
+    async fn test_Macd_calculate() {
+        let signal = Macd {};
+        let series: Vec<f64> = (1..=40).map(|i| i as f64).collect();
+
+        let macd = signal.calculate(&series).await.unwrap();
+        let (last_macd, last_signal, last_histogram) = *macd.last().unwrap();
+        assert_eq!(last_histogram, last_macd - last_signal);
+    }
+
+    #[tokio::test]
+    async fn test_BollingerBands_calculate() {
+        let signal = BollingerBands {
+            window_size: 3,
+            num_std_dev: 2.0,
+        };
+        assert_eq!(signal.calculate(&[]).await, None);
+
+        let bands = signal.calculate(&[2.0, 4.0, 6.0]).await.unwrap();
+        assert_eq!(bands, vec![(4.0, 7.265986323710904, 0.7340136762890959)]);
+    }
+
+    #[tokio::test]
+    async fn test_SmaCrossoverStrategy_generate() {
+        // A threshold this large never triggers scale-in, isolating the plain entry/exit path.
+        let strategy = SmaCrossoverStrategy {
+            fast_window: 2,
+            slow_window: 3,
+            scale_in_threshold: 100.0,
+        };
+
+        // Falls from 10 to 1 (fast stays below slow), climbs back to 10 (fast crosses above
+        // slow: GoLong), then falls again (fast crosses below slow: ExitLong).
+        let closes: Vec<f64> = (1..=10)
+            .rev()
+            .chain(2..=10)
+            .chain((1..=9).rev())
+            .map(|n| n as f64)
+            .collect();
+
+        let signals = strategy.generate(&closes).await;
+        assert_eq!(signals, vec![TradeSignal::GoLong, TradeSignal::ExitLong]);
+    }
+
+    #[tokio::test]
+    async fn test_SmaCrossoverStrategy_generate_go_short_from_flat() {
+        let strategy = SmaCrossoverStrategy {
+            fast_window: 2,
+            slow_window: 3,
+            scale_in_threshold: 0.5,
+        };
+
+        // Rises just enough to put the fast SMA above the slow one, then falls back below
+        // while no position is open yet, so the down-cross must open a short, not exit a long.
+        let closes = vec![1.0, 2.0, 3.0, 2.0, 1.0];
+
+        let signals = strategy.generate(&closes).await;
+        assert_eq!(signals, vec![TradeSignal::GoShort]);
+    }
+
+    #[tokio::test]
+    async fn test_SmaCrossoverStrategy_generate_reverses_and_scales_in() {
+        let strategy = SmaCrossoverStrategy {
+            fast_window: 2,
+            slow_window: 3,
+            scale_in_threshold: 0.2,
+        };
+
+        // Down-cross into a short while flat, then a sustained climb: an up-cross flips the
+        // short back to long, and the continuing climb scales into the long repeatedly without
+        // any further crossing.
+        let closes = vec![1.0, 2.0, 3.0, 2.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+
+        let signals = strategy.generate(&closes).await;
+        assert_eq!(
+            signals,
+            vec![
+                TradeSignal::GoShort,
+                TradeSignal::GoLong,
+                TradeSignal::ScaleIn,
+                TradeSignal::ScaleIn,
+                TradeSignal::ScaleIn,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_format_stats() {
+        let stats = vec![StockStats::new("AAPL".to_owned(), vec![1.0, 2.0, 3.0], Utc::now()).await];
+
+        let csv = format_stats(OutputFormat::Csv, &stats);
+        assert!(csv.starts_with(&stats[0].period_start));
+        assert!(csv.contains("AAPL"));
+
+        let json = format_stats(OutputFormat::Json, &stats);
+        assert!(json.contains("\"symbol\":\"AAPL\""));
+
+        let table = format_stats(OutputFormat::Table, &stats);
+        assert_eq!(table.lines().count(), 2);
+    }
 }